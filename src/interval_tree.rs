@@ -1,19 +1,39 @@
+mod by;
+mod comparator;
+mod cursor;
 mod find;
+mod iter;
+mod monoid;
 mod node;
 
-pub use self::find::Find;
+pub use self::by::IntervalTreeBy;
+pub use self::comparator::{Comparator, OrdComparator};
+pub use self::cursor::Cursor;
+pub use self::find::{Entry, EntryMut, Find, FindMut};
+pub use self::iter::Iter;
+pub use self::monoid::{Monoid, Summarize};
 
-use std::{cmp, ops::RangeInclusive};
+use std::ops::RangeInclusive;
 
-use self::node::Node;
+use self::node::{Node, NodeId};
 
 /// A self-balancing binary search tree optimized to hold interval-value pairs.
-#[derive(Default)]
-pub struct IntervalTree<K: Clone + Ord, V> {
-    root: Option<Box<Node<K, V>>>,
+///
+/// Nodes are stored in a flat arena rather than as individually boxed allocations, keeping the
+/// tree contiguous in memory and making it cheap to [`Clone`]. Keys are ordered using their
+/// intrinsic [`Ord`] implementation; use [`IntervalTreeBy`] directly for a custom [`Comparator`].
+#[derive(Clone)]
+pub struct IntervalTree<K: Clone + Ord, V, S: Monoid = ()> {
+    inner: IntervalTreeBy<K, V, OrdComparator, S>,
 }
 
-impl<K: Clone + Ord, V> IntervalTree<K, V> {
+impl<K: Clone + Ord, V, S: Monoid> Default for IntervalTree<K, V, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Clone + Ord, V, S: Monoid> IntervalTree<K, V, S> {
     /// Creates an empty interval tree.
     ///
     /// # Examples
@@ -23,7 +43,56 @@ impl<K: Clone + Ord, V> IntervalTree<K, V> {
     /// let _tree: IntervalTree<u64, &str> = IntervalTree::new();
     /// ```
     pub fn new() -> Self {
-        Self { root: None }
+        Self {
+            inner: IntervalTreeBy::new(OrdComparator),
+        }
+    }
+
+    /// Creates an empty interval tree with at least the given node capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use interval_tree::IntervalTree;
+    /// let _tree: IntervalTree<u64, &str> = IntervalTree::with_capacity(16);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: IntervalTreeBy::with_capacity(OrdComparator, capacity),
+        }
+    }
+
+    /// Builds a height-balanced tree from an iterator of interval-value pairs, pre-sorted by
+    /// interval start.
+    ///
+    /// Unlike repeated [`IntervalTree::insert`], this builds the tree bottom-up from the sorted
+    /// sequence in a single O(n) pass, without any rotations.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `iter` is not non-decreasing by interval start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use interval_tree::IntervalTree;
+    ///
+    /// let tree = IntervalTree::from_sorted_iter([
+    ///     (2..=6, "elm"),
+    ///     (3..=9, "walnut"),
+    ///     (7..=13, "ash"),
+    /// ]);
+    ///
+    /// assert_eq!(tree.find(8..=10).count(), 2);
+    /// ```
+    pub fn from_sorted_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (RangeInclusive<K>, V)>,
+        V: Summarize<S>,
+    {
+        Self {
+            inner: IntervalTreeBy::from_sorted_iter(OrdComparator, iter),
+        }
     }
 
     /// Adds an interval-value pair into the tree.
@@ -41,12 +110,11 @@ impl<K: Clone + Ord, V> IntervalTree<K, V> {
     /// tree.insert(7..=13, "ash");
     /// tree.insert(7..=13, "walnut");
     /// ```
-    pub fn insert(&mut self, key: RangeInclusive<K>, value: V) {
-        self.root = if let Some(root) = self.root.take() {
-            Some(insert(root, key, value))
-        } else {
-            Some(Box::new(Node::new(key, value)))
-        };
+    pub fn insert(&mut self, key: RangeInclusive<K>, value: V)
+    where
+        V: Summarize<S>,
+    {
+        self.inner.insert(key, value);
     }
 
     /// Returns an iterator visiting nodes that intersect the given key.
@@ -74,145 +142,147 @@ impl<K: Clone + Ord, V> IntervalTree<K, V> {
     ///
     /// assert!(iter.next().is_none());
     /// ```
-    pub fn find(&self, key: RangeInclusive<K>) -> Find<K, V> {
-        let nodes = self.root.iter().map::<&Node<K, V>, _>(|n| n).collect();
-        Find::new(nodes, key)
-    }
-}
-
-fn height<K: Clone + Ord, V>(root: &Option<Box<Node<K, V>>>) -> u32 {
-    root.as_ref().map_or(0, |n| n.height)
-}
-
-enum BalanceFactor {
-    LeftHeavy,
-    Balanced,
-    RightHeavy,
-}
-
-fn balance_factor<K: Clone + Ord, V>(root: &Node<K, V>) -> BalanceFactor {
-    let left_height = height(&root.left) as i32;
-    let right_height = height(&root.right) as i32;
-
-    if left_height > right_height && left_height - right_height >= 2 {
-        BalanceFactor::LeftHeavy
-    } else if left_height < right_height && right_height - left_height >= 2 {
-        BalanceFactor::RightHeavy
-    } else {
-        BalanceFactor::Balanced
+    pub fn find(&self, key: RangeInclusive<K>) -> Find<'_, K, V, OrdComparator, S> {
+        self.inner.find(key)
     }
-}
 
-fn update_height<K: Clone + Ord, V>(root: &mut Node<K, V>) {
-    let left_height = height(&root.left);
-    let right_height = height(&root.right);
-    root.height = cmp::max(left_height, right_height) + 1;
-}
-
-fn update_max<K: Clone + Ord, V>(root: &mut Node<K, V>) {
-    root.max = root.key.end().clone();
-
-    if let Some(ref left) = root.left {
-        if left.max > root.max {
-            root.max = left.max.clone();
-        }
-    }
-
-    if let Some(ref right) = root.right {
-        if right.max > root.max {
-            root.max = right.max.clone();
-        }
+    /// Returns an iterator visiting nodes that intersect the given key, yielding mutable value
+    /// references.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use interval_tree::IntervalTree;
+    ///
+    /// let mut tree = IntervalTree::new();
+    ///
+    /// tree.insert(2..=6, "elm");
+    /// tree.insert(7..=13, "ash");
+    ///
+    /// for mut entry in tree.find_mut(5..=8) {
+    ///     *entry.get_mut() = "birch";
+    /// }
+    ///
+    /// assert_eq!(tree.find(2..=6).next().unwrap().get(), &"birch");
+    /// ```
+    pub fn find_mut(&mut self, key: RangeInclusive<K>) -> FindMut<'_, K, V, OrdComparator, S> {
+        self.inner.find_mut(key)
     }
-}
-
-fn rotate_left<K: Clone + Ord, V>(mut root: Box<Node<K, V>>) -> Box<Node<K, V>> {
-    let mut new_root = root.right.take().expect("invalid tree");
-
-    root.right = new_root.left.take();
-    update_height(&mut root);
-    update_max(&mut root);
 
-    new_root.left = Some(root);
-    update_height(&mut new_root);
-    update_max(&mut new_root);
-
-    new_root
-}
-
-fn balance_left_heavy_tree<K: Clone + Ord, V>(mut root: Box<Node<K, V>>) -> Box<Node<K, V>> {
-    let left = root.left.take().expect("invalid tree");
-
-    if height(&left.left) < height(&left.right) {
-        let new_left = rotate_left(left);
-        root.left = Some(new_left);
-        update_height(&mut root);
-        update_max(&mut root);
-    } else {
-        root.left = Some(left);
+    /// Removes the entry with the given key, returning its value if it was present.
+    ///
+    /// The key must match exactly, i.e. both its start and end must equal those of an interval
+    /// already in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use interval_tree::IntervalTree;
+    ///
+    /// let mut tree = IntervalTree::new();
+    ///
+    /// tree.insert(2..=6, "elm");
+    /// tree.insert(7..=13, "ash");
+    ///
+    /// assert_eq!(tree.remove(&(2..=6)), Some("elm"));
+    /// assert_eq!(tree.remove(&(2..=6)), None);
+    /// assert_eq!(tree.find(0..=20).count(), 1);
+    /// ```
+    pub fn remove(&mut self, key: &RangeInclusive<K>) -> Option<V>
+    where
+        V: Summarize<S>,
+    {
+        self.inner.remove(key)
     }
 
-    rotate_right(root)
-}
-
-fn rotate_right<K: Clone + Ord, V>(mut root: Box<Node<K, V>>) -> Box<Node<K, V>> {
-    let mut new_root = root.left.take().expect("invalid tree");
-
-    root.left = new_root.right.take();
-    update_height(&mut root);
-    update_max(&mut root);
-
-    new_root.right = Some(root);
-    update_height(&mut new_root);
-    update_max(&mut new_root);
-
-    new_root
-}
-
-fn balance_right_heavy_tree<K: Clone + Ord, V>(mut root: Box<Node<K, V>>) -> Box<Node<K, V>> {
-    let right = root.right.take().expect("invalid tree");
-
-    if height(&right.left) > height(&right.right) {
-        let new_right = rotate_right(right);
-        root.right = Some(new_right);
-        update_height(&mut root);
-        update_max(&mut root);
-    } else {
-        root.right = Some(right);
+    /// Returns an iterator visiting every entry in ascending order by interval start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use interval_tree::IntervalTree;
+    ///
+    /// let mut tree = IntervalTree::new();
+    ///
+    /// tree.insert(7..=13, "ash");
+    /// tree.insert(2..=6, "elm");
+    ///
+    /// let entries: Vec<_> = tree.iter().map(|entry| *entry.get()).collect();
+    /// assert_eq!(entries, ["elm", "ash"]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, V, S> {
+        self.inner.iter()
     }
 
-    rotate_left(root)
-}
-
-fn balance<K: Clone + Ord, V>(root: Box<Node<K, V>>) -> Box<Node<K, V>> {
-    match balance_factor(&root) {
-        BalanceFactor::LeftHeavy => balance_left_heavy_tree(root),
-        BalanceFactor::Balanced => root,
-        BalanceFactor::RightHeavy => balance_right_heavy_tree(root),
+    /// Returns a cursor positioned at the first entry whose interval starts at or after `target`.
+    ///
+    /// The cursor is unpositioned, i.e. [`Cursor::current`] returns `None`, if no interval starts
+    /// at or after `target`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use interval_tree::IntervalTree;
+    ///
+    /// let mut tree = IntervalTree::new();
+    ///
+    /// tree.insert(2..=6, "elm");
+    /// tree.insert(7..=13, "ash");
+    ///
+    /// let mut cursor = tree.cursor(&5);
+    /// assert_eq!(cursor.current().unwrap().get(), &"ash");
+    /// assert!(cursor.advance().is_none());
+    /// ```
+    pub fn cursor(&self, target: &K) -> Cursor<'_, K, V, S> {
+        self.inner.cursor(target)
     }
-}
 
-fn insert<K, V>(mut root: Box<Node<K, V>>, key: RangeInclusive<K>, value: V) -> Box<Node<K, V>>
-where
-    K: Clone + Ord,
-{
-    if key.start() <= root.key.start() {
-        root.left = if let Some(left) = root.left.take() {
-            Some(insert(left, key, value))
-        } else {
-            Some(Box::new(Node::new(key, value)))
-        }
-    } else if key.start() > root.key.start() {
-        root.right = if let Some(right) = root.right.take() {
-            Some(insert(right, key, value))
-        } else {
-            Some(Box::new(Node::new(key, value)))
-        }
+    /// Aggregates the summaries of every value whose interval overlaps the given key.
+    ///
+    /// Unlike [`IntervalTree::find`], this never materializes the overlapping entries: whenever
+    /// an entire subtree is known to fall outside the query (via its cached `max`/`min`), it
+    /// contributes [`Monoid::identity`], and whenever it falls strictly inside the query (via the
+    /// same bounds), its cached subtree summary is used directly instead of descending further.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use interval_tree::{IntervalTree, Monoid, Summarize};
+    ///
+    /// #[derive(Clone, Debug, Eq, PartialEq)]
+    /// struct Count(usize);
+    ///
+    /// impl Monoid for Count {
+    ///     fn identity() -> Self {
+    ///         Count(0)
+    ///     }
+    ///
+    ///     fn combine(&self, other: &Self) -> Self {
+    ///         Count(self.0 + other.0)
+    ///     }
+    /// }
+    ///
+    /// impl Summarize<Count> for &str {
+    ///     fn summarize(&self) -> Count {
+    ///         Count(1)
+    ///     }
+    /// }
+    ///
+    /// let mut tree: IntervalTree<i32, &str, Count> = IntervalTree::new();
+    ///
+    /// tree.insert(2..=6, "elm");
+    /// tree.insert(7..=13, "ash");
+    /// tree.insert(3..=9, "walnut");
+    ///
+    /// assert_eq!(tree.summarize(8..=10), Count(2));
+    /// ```
+    pub fn summarize(&self, key: RangeInclusive<K>) -> S
+    where
+        S: Clone,
+        V: Summarize<S>,
+    {
+        self.inner.summarize(key)
     }
-
-    update_height(&mut root);
-    update_max(&mut root);
-
-    balance(root)
 }
 
 #[cfg(test)]
@@ -238,67 +308,55 @@ mod tests {
         tree
     }
 
+    fn node(tree: &IntervalTree<i32, i32>, id: NodeId) -> &Node<i32, i32> {
+        &tree.inner.nodes()[id.index()]
+    }
+
     #[test]
     fn test_insert() {
         let tree = build_tree();
 
-        let root = tree.root.as_ref().unwrap();
+        let root = node(&tree, tree.inner.root().unwrap());
         assert_eq!(root.key, 15..=18);
-        assert_eq!(root.value, 4);
+        assert_eq!(root.value, Some(4));
         assert_eq!(root.max, 24);
         assert_eq!(root.height, 3);
 
-        let node = root.left.as_ref().unwrap();
-        assert_eq!(node.key, 5..=8);
-        assert_eq!(node.value, 1);
-        assert_eq!(node.max, 10);
-        assert_eq!(node.height, 2);
-
-        let node = root
-            .left
-            .as_ref()
-            .and_then(|node| node.left.as_ref())
-            .unwrap();
-        assert_eq!(node.key, 4..=8);
-        assert_eq!(node.value, 3);
-        assert_eq!(node.max, 8);
-        assert_eq!(node.height, 1);
-
-        let node = root
-            .left
-            .as_ref()
-            .and_then(|node| node.right.as_ref())
-            .unwrap();
-        assert_eq!(node.key, 7..=10);
-        assert_eq!(node.value, 5);
-        assert_eq!(node.max, 10);
-        assert_eq!(node.height, 1);
-
-        let node = root.right.as_ref().unwrap();
-        assert_eq!(node.key, 17..=19);
-        assert_eq!(node.value, 0);
-        assert_eq!(node.max, 24);
-        assert_eq!(node.height, 2);
-
-        let node = root
-            .right
-            .as_ref()
-            .and_then(|node| node.left.as_ref())
-            .unwrap();
-        assert_eq!(node.key, 16..=22);
-        assert_eq!(node.value, 6);
-        assert_eq!(node.max, 22);
-        assert_eq!(node.height, 1);
-
-        let node = root
-            .right
-            .as_ref()
-            .and_then(|node| node.right.as_ref())
-            .unwrap();
-        assert_eq!(node.key, 21..=24);
-        assert_eq!(node.value, 2);
-        assert_eq!(node.max, 24);
-        assert_eq!(node.height, 1);
+        let left = node(&tree, root.left.unwrap());
+        assert_eq!(left.key, 5..=8);
+        assert_eq!(left.value, Some(1));
+        assert_eq!(left.max, 10);
+        assert_eq!(left.height, 2);
+
+        let left_left = node(&tree, left.left.unwrap());
+        assert_eq!(left_left.key, 4..=8);
+        assert_eq!(left_left.value, Some(3));
+        assert_eq!(left_left.max, 8);
+        assert_eq!(left_left.height, 1);
+
+        let left_right = node(&tree, left.right.unwrap());
+        assert_eq!(left_right.key, 7..=10);
+        assert_eq!(left_right.value, Some(5));
+        assert_eq!(left_right.max, 10);
+        assert_eq!(left_right.height, 1);
+
+        let right = node(&tree, root.right.unwrap());
+        assert_eq!(right.key, 17..=19);
+        assert_eq!(right.value, Some(0));
+        assert_eq!(right.max, 24);
+        assert_eq!(right.height, 2);
+
+        let right_left = node(&tree, right.left.unwrap());
+        assert_eq!(right_left.key, 16..=22);
+        assert_eq!(right_left.value, Some(6));
+        assert_eq!(right_left.max, 22);
+        assert_eq!(right_left.height, 1);
+
+        let right_right = node(&tree, right.right.unwrap());
+        assert_eq!(right_right.key, 21..=24);
+        assert_eq!(right_right.value, Some(2));
+        assert_eq!(right_right.max, 24);
+        assert_eq!(right_right.height, 1);
     }
 
     #[test]
@@ -326,4 +384,135 @@ mod tests {
         assert_eq!(entries[5].key(), &(4..=8));
         assert_eq!(entries[5].get(), &3);
     }
+
+    #[test]
+    fn test_find_mut() {
+        let mut tree = build_tree();
+
+        for mut entry in tree.find_mut(7..=20) {
+            *entry.get_mut() *= 10;
+        }
+
+        let entries: Vec<_> = tree.find(7..=20).collect();
+        assert_eq!(entries.len(), 6);
+        assert!(entries.iter().all(|entry| entry.get() % 10 == 0));
+
+        // `4..=5` overlaps only `4..=8` (not the otherwise-adjacent `5..=8`), confirming it was
+        // among the entries multiplied above.
+        assert_eq!(tree.find(4..=5).next().unwrap().get(), &30);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut tree = build_tree();
+
+        assert_eq!(tree.remove(&(7..=10)), Some(5));
+        assert_eq!(tree.remove(&(7..=10)), None);
+        assert_eq!(tree.find(7..=20).count(), 5);
+
+        assert_eq!(tree.remove(&(15..=18)), Some(4));
+        assert_eq!(tree.find(0..=30).count(), 5);
+
+        for key in [17..=19, 5..=8, 21..=24, 4..=8, 16..=22] {
+            assert!(tree.remove(&key).is_some());
+        }
+
+        assert_eq!(tree.find(0..=30).count(), 0);
+    }
+
+    #[test]
+    fn test_clone() {
+        let tree = build_tree();
+        let cloned = tree.clone();
+        let entries: Vec<_> = cloned.find(7..=20).collect();
+        assert_eq!(entries.len(), 6);
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    struct Count(usize);
+
+    impl Monoid for Count {
+        fn identity() -> Self {
+            Count(0)
+        }
+
+        fn combine(&self, other: &Self) -> Self {
+            Count(self.0 + other.0)
+        }
+    }
+
+    // `i32` already has the blanket `Summarize<()>` impl used by plain trees elsewhere in this
+    // crate; a second `Summarize<Count>` impl would leave `S` ambiguous for the unannotated
+    // `IntervalTree::new()` trees in other tests, so the counted value is wrapped in a newtype
+    // instead.
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    struct Weighted(i32);
+
+    impl Summarize<Count> for Weighted {
+        fn summarize(&self) -> Count {
+            Count(1)
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        let mut tree: IntervalTree<i32, Weighted, Count> = IntervalTree::new();
+
+        tree.insert(17..=19, Weighted(0));
+        tree.insert(5..=8, Weighted(1));
+        tree.insert(21..=24, Weighted(2));
+        tree.insert(4..=8, Weighted(3));
+        tree.insert(15..=18, Weighted(4));
+        tree.insert(7..=10, Weighted(5));
+        tree.insert(16..=22, Weighted(6));
+
+        assert_eq!(tree.summarize(7..=20), Count(6));
+        assert_eq!(tree.summarize(100..=200), Count(0));
+        assert_eq!(tree.summarize(0..=30), Count(7));
+    }
+
+    #[test]
+    fn test_custom_comparator() {
+        // `Timestamp` has no `Ord` implementation, so a comparator supplies one.
+        #[derive(Clone)]
+        struct Timestamp(u64);
+
+        let mut tree: IntervalTreeBy<Timestamp, &str, _> =
+            IntervalTreeBy::new(|a: &Timestamp, b: &Timestamp| a.0.cmp(&b.0));
+
+        tree.insert(Timestamp(2)..=Timestamp(6), "elm");
+        tree.insert(Timestamp(7)..=Timestamp(13), "ash");
+        tree.insert(Timestamp(3)..=Timestamp(9), "walnut");
+
+        let entries: Vec<_> = tree.find(Timestamp(8)..=Timestamp(10)).collect();
+        assert_eq!(entries.len(), 2);
+    }
+
+    #[test]
+    fn test_from_sorted_iter() {
+        let tree = IntervalTree::from_sorted_iter([
+            (4..=8, 0),
+            (5..=8, 1),
+            (7..=10, 2),
+            (15..=18, 3),
+            (16..=22, 4),
+            (17..=19, 5),
+            (21..=24, 6),
+        ]);
+
+        let root = node(&tree, tree.inner.root().unwrap());
+        assert_eq!(root.key, 15..=18);
+        assert_eq!(root.max, 24);
+        assert_eq!(root.height, 3);
+
+        let entries: Vec<_> = tree.find(7..=20).collect();
+        assert_eq!(entries.len(), 6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_sorted_iter_panics_when_not_sorted() {
+        let _tree: IntervalTree<i32, i32> =
+            IntervalTree::from_sorted_iter([(5..=8, 0), (4..=8, 1)]);
+    }
 }
@@ -0,0 +1,716 @@
+use std::{cmp, cmp::Ordering, ops::RangeInclusive};
+
+use super::{
+    cursor::Cursor,
+    find::{Find, FindMut},
+    iter::Iter,
+    monoid::{Monoid, Summarize},
+    node::{Node, NodeId},
+    Comparator,
+};
+
+/// A self-balancing binary search tree ordered by a custom [`Comparator`] rather than a key's
+/// intrinsic [`Ord`] implementation.
+///
+/// [`IntervalTree`](super::IntervalTree) is a thin wrapper around this type that orders keys
+/// using [`OrdComparator`](super::OrdComparator).
+#[derive(Clone)]
+pub struct IntervalTreeBy<K: Clone, V, C, S: Monoid = ()> {
+    nodes: Vec<Node<K, V, S>>,
+    root: Option<NodeId>,
+    comparator: C,
+    free: Vec<NodeId>,
+}
+
+impl<K: Clone, V, C: Default, S: Monoid> Default for IntervalTreeBy<K, V, C, S> {
+    /// Creates an empty interval tree ordered by the comparator's default.
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            root: None,
+            comparator: C::default(),
+            free: Vec::new(),
+        }
+    }
+}
+
+impl<K: Clone, V, C, S: Monoid> IntervalTreeBy<K, V, C, S>
+where
+    C: Comparator<K>,
+{
+    /// Creates an empty interval tree ordered by the given comparator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use interval_tree::IntervalTreeBy;
+    ///
+    /// // `Timestamp` has no `Ord` implementation, so a comparator supplies one.
+    /// #[derive(Clone)]
+    /// struct Timestamp(u64);
+    ///
+    /// let _tree: IntervalTreeBy<Timestamp, &str, _> =
+    ///     IntervalTreeBy::new(|a: &Timestamp, b: &Timestamp| a.0.cmp(&b.0));
+    /// ```
+    pub fn new(comparator: C) -> Self {
+        Self {
+            nodes: Vec::new(),
+            root: None,
+            comparator,
+            free: Vec::new(),
+        }
+    }
+
+    /// Creates an empty interval tree with at least the given node capacity.
+    pub fn with_capacity(comparator: C, capacity: usize) -> Self {
+        Self {
+            nodes: Vec::with_capacity(capacity),
+            root: None,
+            comparator,
+            free: Vec::new(),
+        }
+    }
+
+    /// Builds a height-balanced tree from an iterator of interval-value pairs, pre-sorted by
+    /// interval start.
+    ///
+    /// Unlike repeated [`IntervalTreeBy::insert`], this builds the tree bottom-up from the sorted
+    /// sequence in a single O(n) pass, without any rotations.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `iter` is not non-decreasing by interval start according to
+    /// `comparator`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use interval_tree::IntervalTreeBy;
+    ///
+    /// #[derive(Clone)]
+    /// struct Timestamp(u64);
+    ///
+    /// let tree = IntervalTreeBy::from_sorted_iter(
+    ///     |a: &Timestamp, b: &Timestamp| a.0.cmp(&b.0),
+    ///     [
+    ///         (Timestamp(2)..=Timestamp(6), "elm"),
+    ///         (Timestamp(3)..=Timestamp(9), "walnut"),
+    ///         (Timestamp(7)..=Timestamp(13), "ash"),
+    ///     ],
+    /// );
+    ///
+    /// assert_eq!(tree.find(Timestamp(8)..=Timestamp(10)).count(), 2);
+    /// ```
+    pub fn from_sorted_iter<I>(comparator: C, iter: I) -> Self
+    where
+        I: IntoIterator<Item = (RangeInclusive<K>, V)>,
+        V: Summarize<S>,
+    {
+        let items: Vec<_> = iter.into_iter().collect();
+        debug_assert!(is_sorted_by_start(&items, &comparator));
+
+        let len = items.len();
+        let mut nodes = Vec::with_capacity(len);
+        let mut iter = items.into_iter();
+        let root = build(&mut nodes, &mut iter, len, &comparator);
+
+        Self {
+            nodes,
+            root,
+            comparator,
+            free: Vec::new(),
+        }
+    }
+
+    /// Adds an interval-value pair into the tree.
+    ///
+    /// Upon a collision, a new node is added as the left child of the existing node.
+    pub fn insert(&mut self, key: RangeInclusive<K>, value: V)
+    where
+        V: Summarize<S>,
+    {
+        self.root = Some(insert(
+            &mut self.nodes,
+            &mut self.free,
+            self.root,
+            key,
+            value,
+            &self.comparator,
+        ));
+    }
+
+    /// Removes the entry with the given key, returning its value if it was present.
+    ///
+    /// See [`IntervalTree::remove`](super::IntervalTree::remove) for details.
+    pub fn remove(&mut self, key: &RangeInclusive<K>) -> Option<V>
+    where
+        V: Summarize<S>,
+    {
+        let (root, removed) = match self.root {
+            Some(root) => remove(&mut self.nodes, &mut self.free, root, key, &self.comparator),
+            None => (None, None),
+        };
+
+        self.root = root;
+        removed
+    }
+
+    /// Returns an iterator visiting nodes that intersect the given key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use interval_tree::IntervalTreeBy;
+    ///
+    /// #[derive(Clone)]
+    /// struct Timestamp(u64);
+    ///
+    /// let mut tree = IntervalTreeBy::new(|a: &Timestamp, b: &Timestamp| a.0.cmp(&b.0));
+    ///
+    /// tree.insert(Timestamp(2)..=Timestamp(6), "elm");
+    /// tree.insert(Timestamp(7)..=Timestamp(13), "ash");
+    /// tree.insert(Timestamp(3)..=Timestamp(9), "walnut");
+    ///
+    /// let mut iter = tree.find(Timestamp(8)..=Timestamp(10));
+    ///
+    /// let entry = iter.next().unwrap();
+    /// assert_eq!(entry.get(), &"walnut");
+    ///
+    /// let entry = iter.next().unwrap();
+    /// assert_eq!(entry.get(), &"ash");
+    ///
+    /// assert!(iter.next().is_none());
+    /// ```
+    pub fn find(&self, key: RangeInclusive<K>) -> Find<'_, K, V, C, S> {
+        let stack = self.root.into_iter().collect();
+        Find::new(&self.nodes, stack, key, &self.comparator)
+    }
+
+    /// Returns an iterator visiting nodes that intersect the given key, yielding mutable value
+    /// references.
+    ///
+    /// See [`IntervalTree::find_mut`](super::IntervalTree::find_mut) for details.
+    pub fn find_mut(&mut self, key: RangeInclusive<K>) -> FindMut<'_, K, V, C, S> {
+        let stack = self.root.into_iter().collect();
+        FindMut::new(&mut self.nodes, stack, key, &self.comparator)
+    }
+
+    /// Returns an iterator visiting every entry in ascending order by interval start.
+    ///
+    /// See [`IntervalTree::iter`](super::IntervalTree::iter) for details.
+    pub fn iter(&self) -> Iter<'_, K, V, S> {
+        Iter::new(&self.nodes, self.root)
+    }
+
+    /// Returns a cursor positioned at the first entry whose interval starts at or after `target`.
+    ///
+    /// See [`IntervalTree::cursor`](super::IntervalTree::cursor) for details.
+    pub fn cursor(&self, target: &K) -> Cursor<'_, K, V, S> {
+        Cursor::seek(&self.nodes, self.root, target, &self.comparator)
+    }
+
+    /// Aggregates the summaries of every value whose interval overlaps the given key.
+    ///
+    /// See [`IntervalTree::summarize`](super::IntervalTree::summarize) for details.
+    pub fn summarize(&self, key: RangeInclusive<K>) -> S
+    where
+        S: Clone,
+        V: Summarize<S>,
+    {
+        summarize(&self.nodes, self.root, &key, &self.comparator)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn nodes(&self) -> &[Node<K, V, S>] {
+        &self.nodes
+    }
+
+    #[cfg(test)]
+    pub(crate) fn root(&self) -> Option<NodeId> {
+        self.root
+    }
+}
+
+fn height<K: Clone, V, S: Monoid>(nodes: &[Node<K, V, S>], id: Option<NodeId>) -> u32 {
+    id.map_or(0, |id| nodes[id.index()].height)
+}
+
+enum BalanceFactor {
+    LeftHeavy,
+    Balanced,
+    RightHeavy,
+}
+
+fn balance_factor<K: Clone, V, S: Monoid>(nodes: &[Node<K, V, S>], id: NodeId) -> BalanceFactor {
+    let left_height = height(nodes, nodes[id.index()].left) as i32;
+    let right_height = height(nodes, nodes[id.index()].right) as i32;
+
+    if left_height > right_height && left_height - right_height >= 2 {
+        BalanceFactor::LeftHeavy
+    } else if left_height < right_height && right_height - left_height >= 2 {
+        BalanceFactor::RightHeavy
+    } else {
+        BalanceFactor::Balanced
+    }
+}
+
+fn update_height<K: Clone, V, S: Monoid>(nodes: &mut [Node<K, V, S>], id: NodeId) {
+    let left_height = height(nodes, nodes[id.index()].left);
+    let right_height = height(nodes, nodes[id.index()].right);
+    nodes[id.index()].height = cmp::max(left_height, right_height) + 1;
+}
+
+fn update_max<K: Clone, V, S: Monoid, C: Comparator<K>>(
+    nodes: &mut [Node<K, V, S>],
+    id: NodeId,
+    cmp: &C,
+) {
+    let mut max = nodes[id.index()].key.end().clone();
+
+    if let Some(left) = nodes[id.index()].left {
+        if cmp.compare(&nodes[left.index()].max, &max) == Ordering::Greater {
+            max = nodes[left.index()].max.clone();
+        }
+    }
+
+    if let Some(right) = nodes[id.index()].right {
+        if cmp.compare(&nodes[right.index()].max, &max) == Ordering::Greater {
+            max = nodes[right.index()].max.clone();
+        }
+    }
+
+    nodes[id.index()].max = max;
+}
+
+fn update_min<K: Clone, V, S: Monoid, C: Comparator<K>>(
+    nodes: &mut [Node<K, V, S>],
+    id: NodeId,
+    cmp: &C,
+) {
+    let mut min = nodes[id.index()].key.start().clone();
+
+    if let Some(left) = nodes[id.index()].left {
+        if cmp.compare(&nodes[left.index()].min, &min) == Ordering::Less {
+            min = nodes[left.index()].min.clone();
+        }
+    }
+
+    if let Some(right) = nodes[id.index()].right {
+        if cmp.compare(&nodes[right.index()].min, &min) == Ordering::Less {
+            min = nodes[right.index()].min.clone();
+        }
+    }
+
+    nodes[id.index()].min = min;
+}
+
+fn update_summary<K: Clone, V, S: Monoid>(nodes: &mut [Node<K, V, S>], id: NodeId)
+where
+    V: Summarize<S>,
+{
+    let mut summary = nodes[id.index()]
+        .value
+        .as_ref()
+        .expect("node value missing")
+        .summarize();
+
+    if let Some(left) = nodes[id.index()].left {
+        summary = summary.combine(&nodes[left.index()].summary);
+    }
+
+    if let Some(right) = nodes[id.index()].right {
+        summary = summary.combine(&nodes[right.index()].summary);
+    }
+
+    nodes[id.index()].summary = summary;
+}
+
+fn update<K: Clone, V, S: Monoid, C: Comparator<K>>(
+    nodes: &mut [Node<K, V, S>],
+    id: NodeId,
+    cmp: &C,
+) where
+    V: Summarize<S>,
+{
+    update_height(nodes, id);
+    update_max(nodes, id, cmp);
+    update_min(nodes, id, cmp);
+    update_summary(nodes, id);
+}
+
+fn rotate_left<K: Clone, V, S: Monoid, C: Comparator<K>>(
+    nodes: &mut [Node<K, V, S>],
+    root: NodeId,
+    cmp: &C,
+) -> NodeId
+where
+    V: Summarize<S>,
+{
+    let new_root = nodes[root.index()].right.take().expect("invalid tree");
+
+    nodes[root.index()].right = nodes[new_root.index()].left.take();
+    update(nodes, root, cmp);
+
+    nodes[new_root.index()].left = Some(root);
+    update(nodes, new_root, cmp);
+
+    new_root
+}
+
+fn balance_left_heavy_tree<K: Clone, V, S: Monoid, C: Comparator<K>>(
+    nodes: &mut [Node<K, V, S>],
+    root: NodeId,
+    cmp: &C,
+) -> NodeId
+where
+    V: Summarize<S>,
+{
+    let left = nodes[root.index()].left.take().expect("invalid tree");
+
+    if height(nodes, nodes[left.index()].left) < height(nodes, nodes[left.index()].right) {
+        let new_left = rotate_left(nodes, left, cmp);
+        nodes[root.index()].left = Some(new_left);
+        update(nodes, root, cmp);
+    } else {
+        nodes[root.index()].left = Some(left);
+    }
+
+    rotate_right(nodes, root, cmp)
+}
+
+fn rotate_right<K: Clone, V, S: Monoid, C: Comparator<K>>(
+    nodes: &mut [Node<K, V, S>],
+    root: NodeId,
+    cmp: &C,
+) -> NodeId
+where
+    V: Summarize<S>,
+{
+    let new_root = nodes[root.index()].left.take().expect("invalid tree");
+
+    nodes[root.index()].left = nodes[new_root.index()].right.take();
+    update(nodes, root, cmp);
+
+    nodes[new_root.index()].right = Some(root);
+    update(nodes, new_root, cmp);
+
+    new_root
+}
+
+fn balance_right_heavy_tree<K: Clone, V, S: Monoid, C: Comparator<K>>(
+    nodes: &mut [Node<K, V, S>],
+    root: NodeId,
+    cmp: &C,
+) -> NodeId
+where
+    V: Summarize<S>,
+{
+    let right = nodes[root.index()].right.take().expect("invalid tree");
+
+    if height(nodes, nodes[right.index()].left) > height(nodes, nodes[right.index()].right) {
+        let new_right = rotate_right(nodes, right, cmp);
+        nodes[root.index()].right = Some(new_right);
+        update(nodes, root, cmp);
+    } else {
+        nodes[root.index()].right = Some(right);
+    }
+
+    rotate_left(nodes, root, cmp)
+}
+
+fn balance<K: Clone, V, S: Monoid, C: Comparator<K>>(
+    nodes: &mut [Node<K, V, S>],
+    root: NodeId,
+    cmp: &C,
+) -> NodeId
+where
+    V: Summarize<S>,
+{
+    match balance_factor(nodes, root) {
+        BalanceFactor::LeftHeavy => balance_left_heavy_tree(nodes, root, cmp),
+        BalanceFactor::Balanced => root,
+        BalanceFactor::RightHeavy => balance_right_heavy_tree(nodes, root, cmp),
+    }
+}
+
+fn alloc<K, V, S: Monoid>(
+    nodes: &mut Vec<Node<K, V, S>>,
+    free: &mut Vec<NodeId>,
+    key: RangeInclusive<K>,
+    value: V,
+) -> NodeId
+where
+    K: Clone,
+    V: Summarize<S>,
+{
+    let node = Node::new(key, value);
+
+    match free.pop() {
+        Some(id) => {
+            nodes[id.index()] = node;
+            id
+        }
+        None => {
+            nodes.push(node);
+            NodeId::new(nodes.len() - 1)
+        }
+    }
+}
+
+fn insert<K, V, S: Monoid, C: Comparator<K>>(
+    nodes: &mut Vec<Node<K, V, S>>,
+    free: &mut Vec<NodeId>,
+    root: Option<NodeId>,
+    key: RangeInclusive<K>,
+    value: V,
+    cmp: &C,
+) -> NodeId
+where
+    K: Clone,
+    V: Summarize<S>,
+{
+    let root = match root {
+        Some(root) => root,
+        None => return alloc(nodes, free, key, value),
+    };
+
+    if cmp.compare(key.start(), nodes[root.index()].key.start()) != Ordering::Greater {
+        let child = nodes[root.index()].left;
+        let left = insert(nodes, free, child, key, value, cmp);
+        nodes[root.index()].left = Some(left);
+    } else {
+        let child = nodes[root.index()].right;
+        let right = insert(nodes, free, child, key, value, cmp);
+        nodes[root.index()].right = Some(right);
+    }
+
+    update(nodes, root, cmp);
+
+    balance(nodes, root, cmp)
+}
+
+fn remove<K, V, S: Monoid, C: Comparator<K>>(
+    nodes: &mut [Node<K, V, S>],
+    free: &mut Vec<NodeId>,
+    id: NodeId,
+    key: &RangeInclusive<K>,
+    cmp: &C,
+) -> (Option<NodeId>, Option<V>)
+where
+    K: Clone,
+    V: Summarize<S>,
+{
+    let start_order = cmp.compare(key.start(), nodes[id.index()].key.start());
+
+    let is_match = start_order == Ordering::Equal
+        && cmp.compare(key.end(), nodes[id.index()].key.end()) == Ordering::Equal;
+
+    if is_match {
+        let (root, value) = delete(nodes, free, id, cmp);
+        return (root, Some(value));
+    }
+
+    // A collision inserts the new node as the left child of the existing one, but rotations can
+    // later move it to the right, so an equal start that isn't an exact match is searched for on
+    // the left first and then on the right.
+    let removed = if start_order == Ordering::Greater {
+        match nodes[id.index()].right {
+            Some(child) => {
+                let (new_child, removed) = remove(nodes, free, child, key, cmp);
+                nodes[id.index()].right = new_child;
+                removed
+            }
+            None => None,
+        }
+    } else {
+        match nodes[id.index()].left {
+            Some(child) => {
+                let (new_child, removed) = remove(nodes, free, child, key, cmp);
+                nodes[id.index()].left = new_child;
+                removed
+            }
+            None => None,
+        }
+    };
+
+    let removed = match (removed, nodes[id.index()].right) {
+        (None, Some(child)) if start_order == Ordering::Equal => {
+            let (new_child, removed) = remove(nodes, free, child, key, cmp);
+            nodes[id.index()].right = new_child;
+            removed
+        }
+        (removed, _) => removed,
+    };
+
+    if removed.is_none() {
+        return (Some(id), None);
+    }
+
+    update(nodes, id, cmp);
+
+    (Some(balance(nodes, id, cmp)), removed)
+}
+
+/// Unlinks the node at `id` from its subtree, returning the new subtree root and the removed
+/// value.
+fn delete<K, V, S: Monoid, C: Comparator<K>>(
+    nodes: &mut [Node<K, V, S>],
+    free: &mut Vec<NodeId>,
+    id: NodeId,
+    cmp: &C,
+) -> (Option<NodeId>, V)
+where
+    K: Clone,
+    V: Summarize<S>,
+{
+    match (nodes[id.index()].left, nodes[id.index()].right) {
+        (None, None) => {
+            let value = nodes[id.index()].value.take().expect("node value missing");
+            free.push(id);
+            (None, value)
+        }
+        (Some(left), None) => {
+            let value = nodes[id.index()].value.take().expect("node value missing");
+            nodes[id.index()].left = None;
+            free.push(id);
+            (Some(left), value)
+        }
+        (None, Some(right)) => {
+            let value = nodes[id.index()].value.take().expect("node value missing");
+            nodes[id.index()].right = None;
+            free.push(id);
+            (Some(right), value)
+        }
+        (Some(_), Some(right)) => {
+            // Splice in the in-order successor (the leftmost node of the right subtree) and
+            // recompute `max`/`min`/the summary along the affected path, since the subtree
+            // maximum can change even when no rotation is needed.
+            let (new_right, successor_key, successor_value) = remove_min(nodes, free, right, cmp);
+
+            let value = nodes[id.index()]
+                .value
+                .replace(successor_value)
+                .expect("node value missing");
+            nodes[id.index()].key = successor_key;
+            nodes[id.index()].right = new_right;
+
+            update(nodes, id, cmp);
+
+            (Some(balance(nodes, id, cmp)), value)
+        }
+    }
+}
+
+/// Removes and returns the leftmost entry of the subtree rooted at `id`.
+fn remove_min<K, V, S: Monoid, C: Comparator<K>>(
+    nodes: &mut [Node<K, V, S>],
+    free: &mut Vec<NodeId>,
+    id: NodeId,
+    cmp: &C,
+) -> (Option<NodeId>, RangeInclusive<K>, V)
+where
+    K: Clone,
+    V: Summarize<S>,
+{
+    if let Some(left) = nodes[id.index()].left {
+        let (new_left, key, value) = remove_min(nodes, free, left, cmp);
+        nodes[id.index()].left = new_left;
+
+        update(nodes, id, cmp);
+
+        (Some(balance(nodes, id, cmp)), key, value)
+    } else {
+        let right = nodes[id.index()].right;
+        let key = nodes[id.index()].key.clone();
+        let value = nodes[id.index()].value.take().expect("node value missing");
+
+        nodes[id.index()].right = None;
+        free.push(id);
+
+        (right, key, value)
+    }
+}
+
+fn is_sorted_by_start<K: Clone, V, C: Comparator<K>>(
+    items: &[(RangeInclusive<K>, V)],
+    cmp: &C,
+) -> bool {
+    items
+        .windows(2)
+        .all(|w| cmp.compare(w[0].0.start(), w[1].0.start()) != Ordering::Greater)
+}
+
+fn build<K, V, S: Monoid, C: Comparator<K>, I>(
+    nodes: &mut Vec<Node<K, V, S>>,
+    iter: &mut I,
+    len: usize,
+    cmp: &C,
+) -> Option<NodeId>
+where
+    K: Clone,
+    V: Summarize<S>,
+    I: Iterator<Item = (RangeInclusive<K>, V)>,
+{
+    if len == 0 {
+        return None;
+    }
+
+    let left_len = len / 2;
+    let left = build(nodes, iter, left_len, cmp);
+
+    let (key, value) = iter.next().expect("sorted iterator exhausted early");
+    nodes.push(Node::new(key, value));
+    let id = NodeId::new(nodes.len() - 1);
+    nodes[id.index()].left = left;
+
+    nodes[id.index()].right = build(nodes, iter, len - left_len - 1, cmp);
+
+    update(nodes, id, cmp);
+
+    Some(id)
+}
+
+fn intersects<K, C: Comparator<K>>(cmp: &C, r: &RangeInclusive<K>, s: &RangeInclusive<K>) -> bool {
+    cmp.compare(r.start(), s.end()) == Ordering::Less && cmp.compare(s.start(), r.end()) == Ordering::Less
+}
+
+fn summarize<K, V, S, C: Comparator<K>>(
+    nodes: &[Node<K, V, S>],
+    id: Option<NodeId>,
+    key: &RangeInclusive<K>,
+    cmp: &C,
+) -> S
+where
+    K: Clone,
+    S: Monoid + Clone,
+    V: Summarize<S>,
+{
+    let id = match id {
+        Some(id) => id,
+        None => return S::identity(),
+    };
+
+    let node = &nodes[id.index()];
+
+    if cmp.compare(&node.max, key.start()) != Ordering::Greater
+        || cmp.compare(key.end(), &node.min) != Ordering::Greater
+    {
+        return S::identity();
+    }
+
+    // Strictly inside the query bounds, so every interval in the subtree intersects it, including
+    // single-point intervals that would otherwise sit on a boundary.
+    if cmp.compare(&node.min, key.start()) == Ordering::Greater
+        && cmp.compare(&node.max, key.end()) == Ordering::Less
+    {
+        return node.summary.clone();
+    }
+
+    let mut summary = summarize(nodes, node.left, key, cmp);
+
+    if intersects(cmp, key, &node.key) {
+        summary = summary.combine(&node.value.as_ref().expect("node value missing").summarize());
+    }
+
+    summary.combine(&summarize(nodes, node.right, key, cmp))
+}
@@ -0,0 +1,48 @@
+use std::cmp::Ordering;
+
+/// Orders the keys of an [`IntervalTreeBy`](super::IntervalTreeBy).
+///
+/// This lets a tree be ordered by something other than a key's intrinsic [`Ord`]
+/// implementation, e.g. in reverse, by a locale-aware collation, or by a projection that only
+/// makes sense given external context.
+pub trait Comparator<K: ?Sized> {
+    /// Compares two keys, analogous to [`Ord::cmp`].
+    fn compare(&self, a: &K, b: &K) -> Ordering;
+}
+
+impl<K, F> Comparator<K> for F
+where
+    F: Fn(&K, &K) -> Ordering,
+{
+    fn compare(&self, a: &K, b: &K) -> Ordering {
+        self(a, b)
+    }
+}
+
+/// A [`Comparator`] that orders keys using their intrinsic [`Ord`] implementation.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OrdComparator;
+
+impl<K: Ord> Comparator<K> for OrdComparator {
+    fn compare(&self, a: &K, b: &K) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ord_comparator() {
+        assert_eq!(OrdComparator.compare(&1, &2), Ordering::Less);
+        assert_eq!(OrdComparator.compare(&2, &2), Ordering::Equal);
+        assert_eq!(OrdComparator.compare(&3, &2), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_fn_comparator() {
+        let reverse = |a: &i32, b: &i32| b.cmp(a);
+        assert_eq!(reverse.compare(&1, &2), Ordering::Greater);
+    }
+}
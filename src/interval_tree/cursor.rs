@@ -0,0 +1,209 @@
+use std::cmp::Ordering;
+
+use super::{Comparator, Entry, Monoid, Node, NodeId};
+
+/// A cursor positioned at an entry in a tree, seekable by key and steppable in ascending or
+/// descending order by interval start.
+///
+/// See [`IntervalTree::cursor`](super::IntervalTree::cursor) for details.
+pub struct Cursor<'a, K: Clone + 'a, V: 'a, S: Monoid + 'a = ()> {
+    nodes: &'a [Node<K, V, S>],
+    // The path from the root down to the current entry, with the current entry's `NodeId` last.
+    // Empty if the cursor isn't positioned on an entry.
+    stack: Vec<NodeId>,
+}
+
+impl<'a, K: Clone + 'a, V: 'a, S: Monoid + 'a> Cursor<'a, K, V, S> {
+    pub(crate) fn seek<C: Comparator<K>>(
+        nodes: &'a [Node<K, V, S>],
+        root: Option<NodeId>,
+        target: &K,
+        cmp: &C,
+    ) -> Self {
+        let mut stack = Vec::new();
+        lower_bound(nodes, root, target, cmp, &mut stack);
+        Self { nodes, stack }
+    }
+
+    /// Returns the entry at the cursor's current position.
+    pub fn current(&self) -> Option<Entry<'a, K, V>> {
+        self.stack.last().map(|&id| entry(self.nodes, id))
+    }
+
+    /// Moves the cursor to the next entry by interval start, returning it.
+    ///
+    /// Named `advance` rather than `next` since a cursor is seekable in both directions and isn't
+    /// an [`Iterator`](std::iter::Iterator). Returns `None` without moving the cursor if it's
+    /// already at the last entry, so a subsequent [`Cursor::retreat`] resumes from there.
+    pub fn advance(&mut self) -> Option<Entry<'a, K, V>> {
+        let id = *self.stack.last()?;
+
+        if let Some(right) = self.nodes[id.index()].right {
+            self.stack.push(right);
+            push_left_spine(self.nodes, right, &mut self.stack);
+            return self.current();
+        }
+
+        // Climb until an ancestor whose left child is the subtree we just came from; that
+        // ancestor is the successor. The stack is left untouched if no such ancestor exists,
+        // i.e. `id` is the last entry.
+        let mut child = id;
+
+        for i in (1..self.stack.len()).rev() {
+            let parent = self.stack[i - 1];
+
+            if self.nodes[parent.index()].left == Some(child) {
+                self.stack.truncate(i);
+                return self.current();
+            }
+
+            child = parent;
+        }
+
+        None
+    }
+
+    /// Moves the cursor to the previous entry by interval start, returning it.
+    ///
+    /// Returns `None` without moving the cursor if it's already at the first entry, so a
+    /// subsequent [`Cursor::advance`] resumes from there.
+    pub fn retreat(&mut self) -> Option<Entry<'a, K, V>> {
+        let id = *self.stack.last()?;
+
+        if let Some(left) = self.nodes[id.index()].left {
+            self.stack.push(left);
+            push_right_spine(self.nodes, left, &mut self.stack);
+            return self.current();
+        }
+
+        // Climb until an ancestor whose right child is the subtree we just came from; that
+        // ancestor is the predecessor. The stack is left untouched if no such ancestor exists,
+        // i.e. `id` is the first entry.
+        let mut child = id;
+
+        for i in (1..self.stack.len()).rev() {
+            let parent = self.stack[i - 1];
+
+            if self.nodes[parent.index()].right == Some(child) {
+                self.stack.truncate(i);
+                return self.current();
+            }
+
+            child = parent;
+        }
+
+        None
+    }
+}
+
+fn entry<K: Clone, V, S: Monoid>(nodes: &[Node<K, V, S>], id: NodeId) -> Entry<'_, K, V> {
+    let node = &nodes[id.index()];
+    Entry::new(&node.key, node.value.as_ref().expect("node value missing"))
+}
+
+fn push_left_spine<K: Clone, V, S: Monoid>(
+    nodes: &[Node<K, V, S>],
+    id: NodeId,
+    stack: &mut Vec<NodeId>,
+) {
+    let mut next = nodes[id.index()].left;
+
+    while let Some(id) = next {
+        stack.push(id);
+        next = nodes[id.index()].left;
+    }
+}
+
+fn push_right_spine<K: Clone, V, S: Monoid>(
+    nodes: &[Node<K, V, S>],
+    id: NodeId,
+    stack: &mut Vec<NodeId>,
+) {
+    let mut next = nodes[id.index()].right;
+
+    while let Some(id) = next {
+        stack.push(id);
+        next = nodes[id.index()].right;
+    }
+}
+
+/// Descends toward the leftmost node whose key starts at or after `target`, leaving `stack`
+/// holding the path from the root to that node (empty if none qualifies).
+fn lower_bound<K: Clone, V, S: Monoid, C: Comparator<K>>(
+    nodes: &[Node<K, V, S>],
+    id: Option<NodeId>,
+    target: &K,
+    cmp: &C,
+    stack: &mut Vec<NodeId>,
+) -> bool {
+    let id = match id {
+        Some(id) => id,
+        None => return false,
+    };
+
+    stack.push(id);
+
+    if cmp.compare(nodes[id.index()].key.start(), target) == Ordering::Less {
+        if lower_bound(nodes, nodes[id.index()].right, target, cmp, stack) {
+            return true;
+        }
+
+        stack.pop();
+        false
+    } else if lower_bound(nodes, nodes[id.index()].left, target, cmp, stack) {
+        true
+    } else {
+        // `id` is already the best candidate and is already on the stack.
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interval_tree::IntervalTree;
+
+    fn build_tree() -> IntervalTree<i32, i32> {
+        let mut tree = IntervalTree::new();
+
+        tree.insert(17..=19, 0);
+        tree.insert(5..=8, 1);
+        tree.insert(21..=24, 2);
+        tree.insert(4..=8, 3);
+        tree.insert(15..=18, 4);
+        tree.insert(7..=10, 5);
+        tree.insert(16..=22, 6);
+
+        tree
+    }
+
+    #[test]
+    fn test_seek() {
+        let tree = build_tree();
+
+        let cursor = tree.cursor(&10);
+        assert_eq!(cursor.current().unwrap().key(), &(15..=18));
+
+        let cursor = tree.cursor(&100);
+        assert!(cursor.current().is_none());
+    }
+
+    #[test]
+    fn test_advance_and_retreat() {
+        let tree = build_tree();
+        let mut cursor = tree.cursor(&0);
+
+        let mut starts = vec![*cursor.current().unwrap().key().start()];
+
+        while let Some(entry) = cursor.advance() {
+            starts.push(*entry.key().start());
+        }
+
+        assert_eq!(starts, [4, 5, 7, 15, 16, 17, 21]);
+
+        while let Some(entry) = cursor.retreat() {
+            starts.push(*entry.key().start());
+        }
+
+        assert_eq!(starts, [4, 5, 7, 15, 16, 17, 21, 17, 16, 15, 7, 5, 4]);
+    }
+}
@@ -1,73 +1,164 @@
 mod entry;
 
-pub use self::entry::Entry;
+pub use self::entry::{Entry, EntryMut};
 
-use std::ops::RangeInclusive;
+use std::{marker::PhantomData, ops::RangeInclusive};
 
-use super::Node;
+use super::{Comparator, Monoid, Node, NodeId, OrdComparator};
 
-pub struct Find<'a, K: Clone + Ord + 'a, V: 'a> {
-    nodes: Vec<&'a Node<K, V>>,
+pub struct Find<'a, K: Clone + 'a, V: 'a, C = OrdComparator, S: Monoid + 'a = ()> {
+    nodes: &'a [Node<K, V, S>],
+    stack: Vec<NodeId>,
     key: RangeInclusive<K>,
+    comparator: &'a C,
 }
 
-impl<'a, K: Clone + Ord + 'a, V: 'a> Find<'a, K, V> {
-    pub(crate) fn new(nodes: Vec<&'a Node<K, V>>, key: RangeInclusive<K>) -> Self {
-        Self { nodes, key }
+impl<'a, K: Clone + 'a, V: 'a, C, S: Monoid + 'a> Find<'a, K, V, C, S> {
+    pub(crate) fn new(
+        nodes: &'a [Node<K, V, S>],
+        stack: Vec<NodeId>,
+        key: RangeInclusive<K>,
+        comparator: &'a C,
+    ) -> Self {
+        Self {
+            nodes,
+            stack,
+            key,
+            comparator,
+        }
     }
 }
 
-impl<'a, K: Clone + Ord + 'a, V: 'a> Iterator for Find<'a, K, V> {
+impl<'a, K: Clone + 'a, V: 'a, C, S: Monoid + 'a> Iterator for Find<'a, K, V, C, S>
+where
+    C: Comparator<K>,
+{
     type Item = Entry<'a, K, V>;
 
     fn next(&mut self) -> Option<Entry<'a, K, V>> {
+        use std::cmp::Ordering;
+
         loop {
-            let node = self.nodes.pop()?;
+            let id = self.stack.pop()?;
+            let node = &self.nodes[id.index()];
 
-            if *self.key.start() >= node.max {
+            if self.comparator.compare(self.key.start(), &node.max) != Ordering::Less {
                 continue;
             }
 
-            if let Some(ref left) = node.left {
-                self.nodes.push(left);
+            if let Some(left) = node.left {
+                self.stack.push(left);
             }
 
-            if self.key.end() <= node.key.start() {
+            if self.comparator.compare(self.key.end(), node.key.start()) != Ordering::Greater {
                 continue;
             }
 
-            if let Some(ref right) = node.right {
-                self.nodes.push(right);
+            if let Some(right) = node.right {
+                self.stack.push(right);
             }
 
-            if intersects(&self.key, &node.key) {
-                #[allow(deprecated)]
-                return Some(Entry {
-                    key: &node.key,
-                    value: &node.value,
-                });
+            if intersects(self.comparator, &self.key, &node.key) {
+                return Some(Entry::new(
+                    &node.key,
+                    node.value.as_ref().expect("node value missing"),
+                ));
             }
         }
     }
 }
 
-fn intersects<K: Clone + Ord>(r: &RangeInclusive<K>, s: &RangeInclusive<K>) -> bool {
-    r.start() < s.end() && s.start() < r.end()
+fn intersects<K, C: Comparator<K>>(cmp: &C, r: &RangeInclusive<K>, s: &RangeInclusive<K>) -> bool {
+    use std::cmp::Ordering;
+
+    cmp.compare(r.start(), s.end()) == Ordering::Less && cmp.compare(s.start(), r.end()) == Ordering::Less
+}
+
+/// An iterator visiting nodes that intersect a given key, yielding mutable value references.
+///
+/// See [`IntervalTree::find_mut`](super::IntervalTree::find_mut) for details.
+pub struct FindMut<'a, K: Clone + 'a, V: 'a, C = OrdComparator, S: Monoid + 'a = ()> {
+    nodes: *mut Node<K, V, S>,
+    stack: Vec<NodeId>,
+    key: RangeInclusive<K>,
+    comparator: &'a C,
+    _marker: PhantomData<&'a mut [Node<K, V, S>]>,
+}
+
+impl<'a, K: Clone + 'a, V: 'a, C, S: Monoid + 'a> FindMut<'a, K, V, C, S> {
+    pub(crate) fn new(
+        nodes: &'a mut [Node<K, V, S>],
+        stack: Vec<NodeId>,
+        key: RangeInclusive<K>,
+        comparator: &'a C,
+    ) -> Self {
+        Self {
+            nodes: nodes.as_mut_ptr(),
+            stack,
+            key,
+            comparator,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, K: Clone + 'a, V: 'a, C, S: Monoid + 'a> Iterator for FindMut<'a, K, V, C, S>
+where
+    C: Comparator<K>,
+{
+    type Item = EntryMut<'a, K, V>;
+
+    fn next(&mut self) -> Option<EntryMut<'a, K, V>> {
+        use std::cmp::Ordering;
+
+        loop {
+            let id = self.stack.pop()?;
+
+            // SAFETY: a traversal visits each `NodeId` at most once, so the `'a` reference handed
+            // out here never aliases one returned by a previous call to `next`.
+            let node = unsafe { &mut *self.nodes.add(id.index()) };
+
+            if self.comparator.compare(self.key.start(), &node.max) != Ordering::Less {
+                continue;
+            }
+
+            if let Some(left) = node.left {
+                self.stack.push(left);
+            }
+
+            if self.comparator.compare(self.key.end(), node.key.start()) != Ordering::Greater {
+                continue;
+            }
+
+            if let Some(right) = node.right {
+                self.stack.push(right);
+            }
+
+            if intersects(self.comparator, &self.key, &node.key) {
+                return Some(EntryMut::new(
+                    &node.key,
+                    node.value.as_mut().expect("node value missing"),
+                ));
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::interval_tree::OrdComparator;
 
     #[test]
     fn test_intersects() {
-        assert!(intersects(&(0..=8), &(4..=8)));
-        assert!(intersects(&(0..=8), &(-3..=17)));
-        assert!(intersects(&(0..=8), &(-2..=2)));
-        assert!(intersects(&(0..=8), &(5..=13)));
-        assert!(!intersects(&(0..=8), &(-1..=0)));
-        assert!(!intersects(&(0..=8), &(-9..=-2)));
-        assert!(!intersects(&(0..=8), &(14..=20)));
-        assert!(!intersects(&(0..=8), &(8..=9)));
+        let cmp = OrdComparator;
+        assert!(intersects(&cmp, &(0..=8), &(4..=8)));
+        assert!(intersects(&cmp, &(0..=8), &(-3..=17)));
+        assert!(intersects(&cmp, &(0..=8), &(-2..=2)));
+        assert!(intersects(&cmp, &(0..=8), &(5..=13)));
+        assert!(!intersects(&cmp, &(0..=8), &(-1..=0)));
+        assert!(!intersects(&cmp, &(0..=8), &(-9..=-2)));
+        assert!(!intersects(&cmp, &(0..=8), &(14..=20)));
+        assert!(!intersects(&cmp, &(0..=8), &(8..=9)));
     }
 }
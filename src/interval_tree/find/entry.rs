@@ -1,12 +1,12 @@
 use std::ops::RangeInclusive;
 
 #[derive(Debug)]
-pub struct Entry<'a, K: Clone + Ord, V> {
+pub struct Entry<'a, K: Clone, V> {
     key: &'a RangeInclusive<K>,
     value: &'a V,
 }
 
-impl<'a, K: Clone + Ord, V> Entry<'a, K, V> {
+impl<'a, K: Clone, V> Entry<'a, K, V> {
     pub(crate) fn new(key: &'a RangeInclusive<K>, value: &'a V) -> Self {
         Self { key, value }
     }
@@ -23,3 +23,31 @@ impl<'a, K: Clone + Ord, V> Entry<'a, K, V> {
         self.value
     }
 }
+
+/// A mutable entry yielded by [`FindMut`](super::FindMut).
+#[derive(Debug)]
+pub struct EntryMut<'a, K: Clone, V> {
+    key: &'a RangeInclusive<K>,
+    value: &'a mut V,
+}
+
+impl<'a, K: Clone, V> EntryMut<'a, K, V> {
+    pub(crate) fn new(key: &'a RangeInclusive<K>, value: &'a mut V) -> Self {
+        Self { key, value }
+    }
+
+    /// Returns a reference to the key in the entry.
+    pub fn key(&self) -> &RangeInclusive<K> {
+        self.key
+    }
+
+    /// Returns a reference to the value in the entry.
+    pub fn get(&self) -> &V {
+        self.value
+    }
+
+    /// Returns a mutable reference to the value in the entry.
+    pub fn get_mut(&mut self) -> &mut V {
+        self.value
+    }
+}
@@ -0,0 +1,65 @@
+use super::{Entry, Monoid, Node, NodeId};
+
+/// An iterator visiting every entry in a tree in ascending order by interval start.
+///
+/// See [`IntervalTree::iter`](super::IntervalTree::iter) for details.
+pub struct Iter<'a, K: Clone + 'a, V: 'a, S: Monoid + 'a = ()> {
+    nodes: &'a [Node<K, V, S>],
+    stack: Vec<NodeId>,
+}
+
+impl<'a, K: Clone + 'a, V: 'a, S: Monoid + 'a> Iter<'a, K, V, S> {
+    pub(crate) fn new(nodes: &'a [Node<K, V, S>], root: Option<NodeId>) -> Self {
+        let mut stack = Vec::new();
+        push_left_spine(nodes, root, &mut stack);
+        Self { nodes, stack }
+    }
+}
+
+impl<'a, K: Clone + 'a, V: 'a, S: Monoid + 'a> Iterator for Iter<'a, K, V, S> {
+    type Item = Entry<'a, K, V>;
+
+    fn next(&mut self) -> Option<Entry<'a, K, V>> {
+        let id = self.stack.pop()?;
+        let node = &self.nodes[id.index()];
+
+        push_left_spine(self.nodes, node.right, &mut self.stack);
+
+        Some(Entry::new(
+            &node.key,
+            node.value.as_ref().expect("node value missing"),
+        ))
+    }
+}
+
+fn push_left_spine<K: Clone, V, S: Monoid>(
+    nodes: &[Node<K, V, S>],
+    mut id: Option<NodeId>,
+    stack: &mut Vec<NodeId>,
+) {
+    while let Some(node_id) = id {
+        stack.push(node_id);
+        id = nodes[node_id.index()].left;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interval_tree::IntervalTree;
+
+    #[test]
+    fn test_iter() {
+        let mut tree = IntervalTree::new();
+
+        tree.insert(17..=19, 0);
+        tree.insert(5..=8, 1);
+        tree.insert(21..=24, 2);
+        tree.insert(4..=8, 3);
+        tree.insert(15..=18, 4);
+        tree.insert(7..=10, 5);
+        tree.insert(16..=22, 6);
+
+        let starts: Vec<_> = tree.iter().map(|entry| *entry.key().start()).collect();
+        assert_eq!(starts, [4, 5, 7, 15, 16, 17, 21]);
+    }
+}
@@ -0,0 +1,65 @@
+/// An associative binary operation with an identity element.
+///
+/// Node summaries are combined pairwise with [`Monoid::combine`], so the aggregate cached at any
+/// subtree root is the combination of the summaries of every node beneath it.
+pub trait Monoid {
+    /// Returns the identity element, i.e. the summary of an empty subtree.
+    fn identity() -> Self;
+
+    /// Combines this summary with another.
+    fn combine(&self, other: &Self) -> Self;
+}
+
+impl Monoid for () {
+    fn identity() -> Self {}
+
+    fn combine(&self, _other: &Self) -> Self {}
+}
+
+/// Maps a value into the monoid summary cached alongside it in the tree.
+///
+/// A blanket implementation covers the trivial `()` monoid, so plain trees (`IntervalTree<K, V>`,
+/// whose `S` defaults to `()`) work for any `V` without an explicit impl. A type may implement
+/// `Summarize<S>` for further monoids alongside the blanket impl, but once it has more than one,
+/// `S` can no longer be inferred from the value type, so every tree holding it, plain trees
+/// included, needs its type written out (e.g. `IntervalTree<K, V>` for a plain tree). Wrapping the type in a newtype and implementing
+/// `Summarize` for the wrapper instead keeps plain trees of the original type inferable.
+pub trait Summarize<S: Monoid> {
+    /// Returns the summary for this value.
+    fn summarize(&self) -> S;
+}
+
+impl<V> Summarize<()> for V {
+    fn summarize(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Eq, PartialEq)]
+    struct Count(usize);
+
+    impl Monoid for Count {
+        fn identity() -> Self {
+            Count(0)
+        }
+
+        fn combine(&self, other: &Self) -> Self {
+            Count(self.0 + other.0)
+        }
+    }
+
+    #[test]
+    fn test_combine() {
+        let a = Count(2);
+        let b = Count(3);
+        assert_eq!(a.combine(&b), Count(5));
+    }
+
+    #[test]
+    fn test_unit_monoid() {
+        assert_eq!(().combine(&()), ());
+        assert_eq!(<() as Monoid>::identity(), ());
+    }
+}
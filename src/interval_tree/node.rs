@@ -1,23 +1,52 @@
-use std::ops::RangeInclusive;
+use std::{num::NonZeroU32, ops::RangeInclusive};
 
-pub(crate) struct Node<K: Clone + Ord, V> {
+use super::{Monoid, Summarize};
+
+/// An index into the arena backing an [`IntervalTree`](super::IntervalTree).
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub(crate) struct NodeId(NonZeroU32);
+
+impl NodeId {
+    pub(crate) fn new(index: usize) -> Self {
+        let n = u32::try_from(index + 1).expect("arena index overflow");
+        Self(NonZeroU32::new(n).expect("arena index overflow"))
+    }
+
+    pub(crate) fn index(self) -> usize {
+        (self.0.get() - 1) as usize
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Node<K: Clone, V, S: Monoid = ()> {
     pub(crate) key: RangeInclusive<K>,
-    pub(crate) value: V,
+    // `None` only once a node has been unlinked by `remove` and is sitting in the free list
+    // awaiting reuse; every reachable node holds `Some`.
+    pub(crate) value: Option<V>,
     pub(crate) max: K,
+    pub(crate) min: K,
     pub(crate) height: u32,
-    pub(crate) left: Option<Box<Node<K, V>>>,
-    pub(crate) right: Option<Box<Node<K, V>>>,
+    pub(crate) summary: S,
+    pub(crate) left: Option<NodeId>,
+    pub(crate) right: Option<NodeId>,
 }
 
-impl<K: Clone + Ord, V> Node<K, V> {
+impl<K: Clone, V, S: Monoid> Node<K, V, S>
+where
+    V: Summarize<S>,
+{
     pub(crate) fn new(key: RangeInclusive<K>, value: V) -> Self {
         let max = key.end().clone();
+        let min = key.start().clone();
+        let summary = value.summarize();
 
         Self {
             key,
-            value,
+            value: Some(value),
             max,
+            min,
             height: 1,
+            summary,
             left: None,
             right: None,
         }